@@ -0,0 +1,84 @@
+//! W3C Trace Context Propagation for Outbound HTTP Calls
+//!
+//! A `reqwest` middleware that injects the active `opentelemetry` span
+//! context into outgoing requests as W3C `traceparent`/`tracestate`
+//! headers, and wraps each request in a client span.
+//!
+//! Usage:
+//!     use reqwest_middleware::ClientBuilder;
+//!     use crate::http_client::TraceContextMiddleware;
+//!
+//!     let client = ClientBuilder::new(reqwest::Client::new())
+//!         .with(TraceContextMiddleware)
+//!         .build();
+//!
+//! Requires [`telemetry::init_tracer`](crate::telemetry::init_tracer) to
+//! have run first, since it installs the global `TraceContextPropagator`
+//! that actually populates the injected headers.
+
+use opentelemetry::global;
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::KeyValue;
+use opentelemetry_http::HeaderInjector;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next, Result};
+use task_local_extensions::Extensions;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Middleware that propagates W3C trace context on outbound requests and
+/// records a client span named after the request's method and URL.
+pub struct TraceContextMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for TraceContextMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let tracer = global::tracer("http-client");
+        // `opentelemetry::Context::current()` reads the raw OTel
+        // thread-local, which `tracing_opentelemetry` never populates — the
+        // ambient span/context instead lives on the `tracing::Span`'s
+        // `Registry` extensions. Pull the parent from there so the client
+        // span (and the propagated `traceparent`) nests under whatever
+        // `tracing` span is actually in flight, instead of starting a new
+        // trace every time.
+        let parent_cx = tracing::Span::current().context();
+        let span = tracer
+            .span_builder(format!("{} {}", req.method(), req.url()))
+            .with_kind(SpanKind::Client)
+            .start_with_context(&tracer, &parent_cx);
+
+        let cx = opentelemetry::Context::current_with_span(span);
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()));
+        });
+
+        let result = next.run(req, extensions).await;
+        let span = cx.span();
+
+        match &result {
+            Ok(response) => {
+                span.set_attribute(KeyValue::new(
+                    "http.status_code",
+                    response.status().as_u16() as i64,
+                ));
+                // Per OTel HTTP semantic conventions, CLIENT spans treat
+                // 4xx as an error too (unlike SERVER spans, where a 4xx is
+                // the caller's fault, not the callee's).
+                if response.status().is_client_error() || response.status().is_server_error() {
+                    span.set_status(Status::error(response.status().to_string()));
+                }
+            }
+            Err(err) => {
+                span.set_status(Status::error(err.to_string()));
+            }
+        }
+
+        span.end();
+
+        result
+    }
+}
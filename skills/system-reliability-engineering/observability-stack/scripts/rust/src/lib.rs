@@ -2,10 +2,15 @@
 //!
 //! OpenTelemetry instrumentation library for Rust applications.
 
+pub mod http_client;
 pub mod telemetry;
 
 #[cfg(feature = "services")]
 pub mod services;
 
-pub use telemetry::{init_tracer, shutdown};
+pub use http_client::TraceContextMiddleware;
+pub use telemetry::{
+    init_meter, init_telemetry, init_tracer, shutdown, Backend, Protocol, TelemetryConfig,
+    TracingHandle,
+};
 
@@ -3,8 +3,9 @@
 //! Demonstrates OpenTelemetry instrumentation in Rust.
 
 use tracing::info;
-use crate::telemetry::init_tracer;
+use crate::telemetry::{init_telemetry, shutdown};
 
+mod http_client;
 mod telemetry;
 mod services;
 
@@ -12,8 +13,8 @@ use services::user_service::UserService;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize OpenTelemetry
-    init_tracer(
+    // Initialize OpenTelemetry tracing and metrics
+    let tracing_handle = init_telemetry(
         "my-service",
         "1.0.0",
         std::env::var("ENV")
@@ -26,11 +27,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Example usage
     let user_service = UserService;
     let user = user_service.get_user_by_id("123".to_string()).await?;
-    
+
     info!(user_id = %user.id, "User fetched successfully");
 
     // Your application code here
 
+    shutdown(&tracing_handle).await;
+
     Ok(())
 }
 
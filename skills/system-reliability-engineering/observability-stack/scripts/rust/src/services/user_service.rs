@@ -2,11 +2,42 @@
 //!
 //! Example service demonstrating custom spans and tracing in Rust.
 
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram};
 use opentelemetry::trace::{Span, Status, Tracer};
 use opentelemetry::global;
 use opentelemetry::KeyValue;
 use tracing::{error, instrument};
 
+/// Request counter for [`UserService::get_user_by_id`], built once and
+/// reused across calls rather than on every invocation of a hot path.
+static REQUEST_COUNTER: OnceLock<Counter<u64>> = OnceLock::new();
+
+/// Latency histogram for [`UserService::get_user_by_id`], built once and
+/// reused across calls rather than on every invocation of a hot path.
+static LATENCY_HISTOGRAM: OnceLock<Histogram<f64>> = OnceLock::new();
+
+fn request_counter() -> &'static Counter<u64> {
+    REQUEST_COUNTER.get_or_init(|| {
+        global::meter("user-service")
+            .u64_counter("user_service.get_user_by_id.requests")
+            .with_description("Number of get_user_by_id requests")
+            .init()
+    })
+}
+
+fn latency_histogram() -> &'static Histogram<f64> {
+    LATENCY_HISTOGRAM.get_or_init(|| {
+        global::meter("user-service")
+            .f64_histogram("user_service.get_user_by_id.duration")
+            .with_description("Duration of get_user_by_id in seconds")
+            .with_unit(opentelemetry::metrics::Unit::new("s"))
+            .init()
+    })
+}
+
 pub struct UserService;
 
 #[derive(Debug)]
@@ -33,29 +64,37 @@ impl UserService {
     /// Result containing User or ServiceError
     #[instrument(skip(self), fields(user.id = %user_id))]
     pub async fn get_user_by_id(&self, user_id: String) -> Result<User, ServiceError> {
+        let start = Instant::now();
+
         let tracer = global::tracer("user-service");
         let mut span = tracer.start("getUserById");
-        
+
         span.set_attribute(KeyValue::new("user.id", user_id.clone()));
         span.set_attribute(KeyValue::new("operation.type", "read"));
 
         span.add_event("Fetching user from database", vec![]);
 
-        match self.fetch_user_from_db(&user_id).await {
-            Ok(user) => {
+        let result = self.fetch_user_from_db(&user_id).await;
+
+        let outcome = match &result {
+            Ok(_) => {
                 span.set_attribute(KeyValue::new("user.found", true));
                 span.set_status(Status::Ok);
-                span.end();
-                Ok(user)
+                "success"
             }
             Err(e) => {
                 span.set_status(Status::error(e.message.clone()));
-                span.record_exception(&e);
+                span.record_exception(e);
                 error!(error = %e.message, "Failed to fetch user");
-                span.end();
-                Err(e)
+                "error"
             }
-        }
+        };
+        span.end();
+
+        latency_histogram().record(start.elapsed().as_secs_f64(), &[]);
+        request_counter().add(1, &[KeyValue::new("outcome", outcome)]);
+
+        result
     }
 
     async fn fetch_user_from_db(&self, user_id: &str) -> Result<User, ServiceError> {
@@ -3,11 +3,28 @@
 //! Initialize OpenTelemetry tracing and metrics for Rust applications.
 //!
 //! Usage:
-//!     use crate::telemetry::init_tracer;
-//!     
-//!     init_tracer("my-service", "1.0.0", "production")?;
+//!     use crate::telemetry::init_telemetry;
+//!
+//!     init_telemetry("my-service", "1.0.0", "production")?;
+//!
+//! Use [`TelemetryConfig`] directly when you need to target a gRPC
+//! collector or override the traces endpoint independently of the
+//! generic `OTEL_EXPORTER_OTLP_ENDPOINT`:
+//!
+//!     use crate::telemetry::{init_tracer, Protocol, TelemetryConfig};
+//!
+//!     let config = TelemetryConfig::new("my-service", "1.0.0", "production")
+//!         .with_protocol(Protocol::Grpc);
+//!     init_tracer(config)?;
+
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use opentelemetry::global;
+#[cfg(feature = "logs")]
+use opentelemetry::sdk::logs::LoggerProvider;
+use opentelemetry::sdk::metrics::{MeterProvider, PeriodicReader};
+use opentelemetry::sdk::runtime;
 use opentelemetry::sdk::trace::TracerProvider;
 use opentelemetry::sdk::Resource;
 use opentelemetry::KeyValue;
@@ -15,49 +32,287 @@ use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_semantic_conventions::resource::{
     DEPLOYMENT_ENVIRONMENT, SERVICE_NAME, SERVICE_VERSION,
 };
+#[cfg(feature = "logs")]
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+use tokio::sync::{mpsc, oneshot};
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::Registry;
+use tracing_subscriber::{EnvFilter, Registry};
 
-/// Initialize OpenTelemetry tracer.
+/// Handle to the global `MeterProvider`, kept around so [`shutdown`] can
+/// flush it; the `opentelemetry` crate only exposes a shutdown hook for the
+/// tracer provider via [`global::shutdown_tracer_provider`].
+static METER_PROVIDER: OnceLock<MeterProvider> = OnceLock::new();
+
+/// Handle to the SDK `TracerProvider`, kept around so [`TracingHandle::force_flush`]
+/// can drain it. Populated for every backend: OTLP and stdout build a
+/// `TracerProvider` directly, while Datadog and Application Insights build
+/// one internally and hand it back via `Tracer::provider()`.
+static TRACER_PROVIDER: OnceLock<TracerProvider> = OnceLock::new();
+
+/// Handle to the global `LoggerProvider`, kept around so [`shutdown`] can
+/// flush it. Only present when built with the `logs` feature.
+#[cfg(feature = "logs")]
+static LOGGER_PROVIDER: OnceLock<LoggerProvider> = OnceLock::new();
+
+/// Runtime handle to a running telemetry pipeline, returned by [`init_tracer`].
 ///
-/// # Arguments
+/// Lets the application bump log/trace verbosity without restarting, and
+/// force a flush of buffered spans before a rolling deploy kills the
+/// process.
+pub struct TracingHandle {
+    reload_handle: reload::Handle<EnvFilter, Registry>,
+    flush_tx: mpsc::Sender<oneshot::Sender<()>>,
+}
+
+impl TracingHandle {
+    /// Replace the active `EnvFilter` directive (e.g. `"debug,hyper=info"`)
+    /// without restarting the process.
+    pub fn set_filter(&self, directive: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let filter = EnvFilter::try_new(directive)?;
+        self.reload_handle.reload(filter)?;
+        Ok(())
+    }
+
+    /// Force the batch span processor to export any buffered spans, waiting
+    /// for the flush to complete.
+    ///
+    /// The flush itself runs on a background task so this can be awaited
+    /// from async code without blocking the executor.
+    pub async fn force_flush(&self) {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        if self.flush_tx.send(ack_tx).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+    }
+}
+
+fn resource(service_name: &str, service_version: &str, environment: &str) -> Resource {
+    Resource::new(vec![
+        KeyValue::new(SERVICE_NAME, service_name.to_string()),
+        KeyValue::new(SERVICE_VERSION, service_version.to_string()),
+        KeyValue::new(DEPLOYMENT_ENVIRONMENT, environment.to_string()),
+    ])
+}
+
+/// OTLP exporter wire protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Export over OTLP/HTTP (the default).
+    Http,
+    /// Export over OTLP/gRPC via `tonic`.
+    Grpc,
+}
+
+impl Protocol {
+    fn from_env_var(var: &str) -> Option<Self> {
+        match std::env::var(var).ok()?.to_lowercase().as_str() {
+            "grpc" => Some(Protocol::Grpc),
+            "http/protobuf" | "http" => Some(Protocol::Http),
+            _ => None,
+        }
+    }
+
+    /// Resolve the protocol from `OTEL_EXPORTER_OTLP_PROTOCOL`, defaulting
+    /// to [`Protocol::Http`] when unset or unrecognized.
+    fn from_env() -> Self {
+        Self::from_env_var("OTEL_EXPORTER_OTLP_PROTOCOL").unwrap_or(Protocol::Http)
+    }
+}
+
+/// Tracing backend to export spans to.
 ///
-/// * `service_name` - Name of the service
-/// * `service_version` - Version of the service
-/// * `environment` - Deployment environment (development, staging, production)
+/// Each non-OTLP variant is gated behind its own Cargo feature so that
+/// consumers only pull in the vendor exporter crates they actually use:
+/// [`Backend::Datadog`] behind `datadog`, [`Backend::ApplicationInsights`]
+/// behind `app-insights`, and [`Backend::Stdout`] behind `stdout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// A vendor-neutral OTLP collector (the default).
+    Otlp,
+    /// A local Datadog Agent. Requires the `datadog` feature.
+    #[cfg(feature = "datadog")]
+    Datadog,
+    /// Azure Application Insights. Requires the `app-insights` feature.
+    #[cfg(feature = "app-insights")]
+    ApplicationInsights,
+    /// Print spans to stdout; useful for local development. Requires the
+    /// `stdout` feature.
+    #[cfg(feature = "stdout")]
+    Stdout,
+}
+
+impl Backend {
+    /// Resolve the backend from `OTEL_TRACES_EXPORTER`, defaulting to
+    /// [`Backend::Otlp`] when unset, unrecognized, or when the matching
+    /// feature isn't compiled in.
+    fn from_env() -> Self {
+        match std::env::var("OTEL_TRACES_EXPORTER")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            #[cfg(feature = "datadog")]
+            "datadog" => Backend::Datadog,
+            #[cfg(feature = "app-insights")]
+            "applicationinsights" => Backend::ApplicationInsights,
+            #[cfg(feature = "stdout")]
+            "stdout" | "console" => Backend::Stdout,
+            _ => Backend::Otlp,
+        }
+    }
+}
+
+/// Builder-style configuration for [`init_tracer`].
 ///
-/// # Returns
+/// Honors the standard `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`,
+/// `OTEL_EXPORTER_OTLP_PROTOCOL`, and `OTEL_TRACES_EXPORTER` environment
+/// variables, falling back to the generic `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// when a signal-specific endpoint isn't set.
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    service_name: String,
+    service_version: String,
+    environment: String,
+    backend: Backend,
+    protocol: Protocol,
+    traces_endpoint: String,
+}
+
+impl TelemetryConfig {
+    /// Create a config from the standard OTLP environment variables,
+    /// defaulting to OTLP/HTTP against `http://localhost:4318/v1/traces`.
+    pub fn new(
+        service_name: impl Into<String>,
+        service_version: impl Into<String>,
+        environment: impl Into<String>,
+    ) -> Self {
+        let protocol = Protocol::from_env();
+        let default_endpoint = match protocol {
+            Protocol::Http => "http://localhost:4318/v1/traces",
+            Protocol::Grpc => "http://localhost:4317",
+        };
+
+        // The signal-specific var is used as-is, but the generic
+        // `OTEL_EXPORTER_OTLP_ENDPOINT` is a base URL shared across signals
+        // per the OTLP spec, so for HTTP it needs the `/v1/traces` path
+        // appended — matching how `init_meter` and `init_logs_bridge`
+        // append `/v1/metrics` and `/v1/logs` respectively. gRPC endpoints
+        // carry no such per-signal path.
+        let traces_endpoint = match std::env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT") {
+            Ok(endpoint) => endpoint,
+            Err(_) => match std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+                Ok(endpoint) if protocol == Protocol::Http => format!("{endpoint}/v1/traces"),
+                Ok(endpoint) => endpoint,
+                Err(_) => default_endpoint.to_string(),
+            },
+        };
+
+        Self {
+            service_name: service_name.into(),
+            service_version: service_version.into(),
+            environment: environment.into(),
+            backend: Backend::from_env(),
+            protocol,
+            traces_endpoint,
+        }
+    }
+
+    /// Override the resolved exporter backend.
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Override the resolved exporter protocol. The tracer consults it only
+    /// when `backend` is [`Backend::Otlp`]; [`init_meter`] and the logs
+    /// pipeline use it whatever the backend.
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Override the resolved traces endpoint.
+    pub fn with_traces_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.traces_endpoint = endpoint.into();
+        self
+    }
+
+    fn resource(&self) -> Resource {
+        resource(&self.service_name, &self.service_version, &self.environment)
+    }
+
+    /// Resource with `service.name` stripped, for backends (Datadog) that
+    /// take the service name through a dedicated pipeline slot instead.
+    #[cfg(feature = "datadog")]
+    fn resource_without_service_name(&self) -> Resource {
+        Resource::new(vec![
+            KeyValue::new(SERVICE_VERSION, self.service_version.clone()),
+            KeyValue::new(DEPLOYMENT_ENVIRONMENT, self.environment.clone()),
+        ])
+    }
+}
+
+/// Build the OpenTelemetry logs pipeline and the `tracing_subscriber` layer
+/// that bridges `tracing` events onto it, so `info!`/`error!` calls are
+/// exported as OTLP `LogRecord`s carrying the active trace and span IDs.
 ///
-/// Result indicating success or failure
-pub fn init_tracer(
-    service_name: &str,
-    service_version: &str,
-    environment: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+/// Resolves `config.protocol` the same way [`init_tracer`]'s OTLP arm does,
+/// so logs agree with traces on transport instead of always shipping over
+/// OTLP/HTTP regardless of how the service is configured.
+#[cfg(feature = "logs")]
+fn init_logs_bridge(
+    config: &TelemetryConfig,
+) -> Result<OpenTelemetryTracingBridge<LoggerProvider, opentelemetry::logs::Logger>, Box<dyn std::error::Error>>
+{
     let otlp_endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
-        .unwrap_or_else(|_| "http://localhost:4318/v1/traces".to_string());
-
-    let otlp_exporter = opentelemetry_otlp::new_exporter()
-        .http()
-        .with_endpoint(otlp_endpoint);
-
-    let tracer_provider = TracerProvider::builder()
-        .with_batch_exporter(otlp_exporter)
-        .with_resource(Resource::new(vec![
-            KeyValue::new(SERVICE_NAME, service_name.to_string()),
-            KeyValue::new(SERVICE_VERSION, service_version.to_string()),
-            KeyValue::new(DEPLOYMENT_ENVIRONMENT, environment.to_string()),
-        ]))
+        .unwrap_or_else(|_| "http://localhost:4318".to_string());
+
+    let otlp_exporter = match config.protocol {
+        Protocol::Http => opentelemetry_otlp::new_exporter()
+            .http()
+            .with_endpoint(format!("{otlp_endpoint}/v1/logs"))
+            .build_log_exporter()?,
+        Protocol::Grpc => opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(otlp_endpoint)
+            .build_log_exporter()?,
+    };
+
+    let logger_provider = LoggerProvider::builder()
+        .with_batch_exporter(otlp_exporter, runtime::Tokio)
+        .with_resource(config.resource())
         .build();
 
-    global::set_tracer_provider(tracer_provider);
+    let bridge = OpenTelemetryTracingBridge::new(&logger_provider);
+
+    let _ = LOGGER_PROVIDER.set(logger_provider);
 
-    // Initialize tracing subscriber
-    let telemetry = tracing_opentelemetry::layer()
-        .with_tracer(global::tracer("my-service"));
+    Ok(bridge)
+}
+
+#[cfg_attr(not(feature = "logs"), allow(unused_variables))]
+fn init_subscriber<T>(
+    config: &TelemetryConfig,
+    tracer: T,
+) -> Result<reload::Handle<EnvFilter, Registry>, Box<dyn std::error::Error>>
+where
+    T: opentelemetry::trace::Tracer + opentelemetry::trace::PreSampledTracer + Send + Sync + 'static,
+{
+    let telemetry = tracing_opentelemetry::layer().with_tracer(tracer);
+
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter_layer, reload_handle) = reload::Layer::new(env_filter);
+
+    #[cfg(feature = "logs")]
+    let logs_layer = Some(init_logs_bridge(config)?);
+    #[cfg(not(feature = "logs"))]
+    let logs_layer: Option<tracing_subscriber::layer::Identity> = None;
 
     let subscriber = Registry::default()
+        .with(filter_layer)
         .with(telemetry)
         .with(
             tracing_subscriber::fmt::layer()
@@ -65,18 +320,233 @@ pub fn init_tracer(
                 .with_target(false)
                 .with_current_span(false),
         )
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into()),
-        );
+        .with(logs_layer);
 
     subscriber.init();
 
+    Ok(reload_handle)
+}
+
+/// Spawn the background task that services [`TracingHandle::force_flush`]
+/// requests, draining the batch span processor off the async executor.
+///
+/// Requires an active Tokio runtime, since it calls [`tokio::spawn`]
+/// internally; returns an error instead of panicking when called from
+/// outside one (e.g. before `#[tokio::main]`'s body starts, or from a
+/// sync `main`).
+fn spawn_flush_task() -> Result<mpsc::Sender<oneshot::Sender<()>>, Box<dyn std::error::Error>> {
+    tokio::runtime::Handle::try_current()
+        .map_err(|_| "spawn_flush_task requires an active Tokio runtime")?;
+
+    let (flush_tx, mut flush_rx) = mpsc::channel::<oneshot::Sender<()>>(1);
+
+    tokio::spawn(async move {
+        while let Some(ack_tx) = flush_rx.recv().await {
+            if let Some(provider) = TRACER_PROVIDER.get() {
+                let provider = provider.clone();
+                let _ = tokio::task::spawn_blocking(move || provider.force_flush()).await;
+            }
+            let _ = ack_tx.send(());
+        }
+    });
+
+    Ok(flush_tx)
+}
+
+/// Initialize OpenTelemetry tracer.
+///
+/// Builds the exporter for `config`'s resolved [`Backend`] (OTLP, Datadog,
+/// Application Insights, or stdout) and, for OTLP, its resolved
+/// [`Protocol`] and endpoint.
+///
+/// Must be called from within an active Tokio runtime (e.g. from the body
+/// of `#[tokio::main]`), since it spawns a background task to service
+/// [`TracingHandle::force_flush`]; calling it before the runtime is up
+/// returns an error rather than panicking.
+///
+/// # Arguments
+///
+/// * `config` - Service identity and exporter configuration
+///
+/// # Returns
+///
+/// A [`TracingHandle`] for runtime filter reloads and flushing, or an error.
+pub fn init_tracer(config: TelemetryConfig) -> Result<TracingHandle, Box<dyn std::error::Error>> {
+    // Propagate W3C trace context on outbound requests so downstream
+    // services joined by `http_client::TraceContextMiddleware` land in the
+    // same trace.
+    global::set_text_map_propagator(opentelemetry::sdk::propagation::TraceContextPropagator::new());
+
+    let flush_tx = spawn_flush_task()?;
+
+    let reload_handle = match config.backend {
+        Backend::Otlp => {
+            let tracer_provider = match config.protocol {
+                Protocol::Http => {
+                    let exporter = opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(config.traces_endpoint.clone());
+
+                    TracerProvider::builder()
+                        .with_batch_exporter(exporter)
+                        .with_resource(config.resource())
+                        .build()
+                }
+                Protocol::Grpc => {
+                    let exporter = opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(config.traces_endpoint.clone());
+
+                    TracerProvider::builder()
+                        .with_batch_exporter(exporter)
+                        .with_resource(config.resource())
+                        .build()
+                }
+            };
+
+            let _ = TRACER_PROVIDER.set(tracer_provider.clone());
+            global::set_tracer_provider(tracer_provider);
+            init_subscriber(&config, global::tracer("my-service"))?
+        }
+        #[cfg(feature = "datadog")]
+        Backend::Datadog => {
+            // Datadog derives the service name from its own pipeline slot
+            // rather than the resource, so the resource is stripped of
+            // `service.name` to avoid tagging the service twice.
+            let tracer = opentelemetry_datadog::new_pipeline()
+                .with_service_name(config.service_name.clone())
+                .with_agent_endpoint(config.traces_endpoint.clone())
+                .with_trace_config(
+                    opentelemetry::sdk::trace::config()
+                        .with_resource(config.resource_without_service_name()),
+                )
+                .install_batch(runtime::Tokio)?;
+
+            let _ = TRACER_PROVIDER.set(tracer.provider());
+            init_subscriber(&config, tracer)?
+        }
+        #[cfg(feature = "app-insights")]
+        Backend::ApplicationInsights => {
+            let connection_string = std::env::var("APPLICATIONINSIGHTS_CONNECTION_STRING")
+                .map_err(|_| "APPLICATIONINSIGHTS_CONNECTION_STRING must be set")?;
+
+            let tracer = opentelemetry_application_insights::new_pipeline_from_connection_string(
+                connection_string,
+            )?
+            .with_client(reqwest::Client::new())
+            .with_trace_config(opentelemetry::sdk::trace::config().with_resource(config.resource()))
+            .install_batch(runtime::Tokio);
+
+            let _ = TRACER_PROVIDER.set(tracer.provider());
+            init_subscriber(&config, tracer)?
+        }
+        #[cfg(feature = "stdout")]
+        Backend::Stdout => {
+            let tracer_provider = TracerProvider::builder()
+                .with_simple_exporter(opentelemetry_stdout::SpanExporter::default())
+                .with_resource(config.resource())
+                .build();
+
+            let _ = TRACER_PROVIDER.set(tracer_provider.clone());
+            global::set_tracer_provider(tracer_provider);
+            init_subscriber(&config, global::tracer("my-service"))?
+        }
+    };
+
+    Ok(TracingHandle {
+        reload_handle,
+        flush_tx,
+    })
+}
+
+/// Initialize OpenTelemetry meter provider.
+///
+/// Builds a `MeterProvider` with a periodic reader that exports counters,
+/// histograms, and gauges over OTLP against `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// (using the `/v1/metrics` path for HTTP), resolving `config`'s
+/// [`Protocol`] the same way [`init_tracer`]'s OTLP arm and
+/// `init_logs_bridge` do, so a service configured for gRPC doesn't have
+/// metrics silently left behind on HTTP. Registers the provider globally
+/// via [`global::set_meter_provider`] and shares the same [`Resource`] as
+/// [`init_tracer`] so metrics and traces correlate on service name,
+/// version, and environment.
+///
+/// # Arguments
+///
+/// * `config` - Service identity and exporter configuration
+///
+/// # Returns
+///
+/// Result indicating success or failure
+pub fn init_meter(config: &TelemetryConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let otlp_endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .unwrap_or_else(|_| "http://localhost:4318".to_string());
+
+    let otlp_exporter = match config.protocol {
+        Protocol::Http => opentelemetry_otlp::new_exporter()
+            .http()
+            .with_endpoint(format!("{otlp_endpoint}/v1/metrics"))
+            .build_metrics_exporter(
+                Box::new(opentelemetry::sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry::sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )?,
+        Protocol::Grpc => opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(otlp_endpoint)
+            .build_metrics_exporter(
+                Box::new(opentelemetry::sdk::metrics::reader::DefaultAggregationSelector::new()),
+                Box::new(opentelemetry::sdk::metrics::reader::DefaultTemporalitySelector::new()),
+            )?,
+    };
+
+    let reader = PeriodicReader::builder(otlp_exporter, runtime::Tokio)
+        .with_interval(Duration::from_secs(30))
+        .build();
+
+    let meter_provider = MeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(config.resource())
+        .build();
+
+    global::set_meter_provider(meter_provider.clone());
+    let _ = METER_PROVIDER.set(meter_provider);
+
     Ok(())
 }
 
-/// Shutdown OpenTelemetry tracer provider.
-pub fn shutdown() {
-    global::shutdown_tracer_provider();
+/// Initialize the combined OpenTelemetry tracing and metrics pipelines.
+///
+/// Convenience wrapper around [`init_tracer`] and [`init_meter`] that shares
+/// the same [`TelemetryConfig`] (service name, version, environment, and
+/// protocol) across both signals. The backend applies to traces only;
+/// metrics always export over OTLP.
+pub fn init_telemetry(
+    service_name: &str,
+    service_version: &str,
+    environment: &str,
+) -> Result<TracingHandle, Box<dyn std::error::Error>> {
+    let config = TelemetryConfig::new(service_name, service_version, environment);
+    let handle = init_tracer(config.clone())?;
+    init_meter(&config)?;
+
+    Ok(handle)
 }
 
+/// Shutdown OpenTelemetry tracer, meter, and (with the `logs` feature)
+/// logger providers.
+///
+/// Forces a flush through `handle` first so in-flight spans are exported
+/// before the batch span processor and meter provider are torn down; this
+/// is what lets a rolling deploy kill the process without losing the tail
+/// of a trace.
+pub async fn shutdown(handle: &TracingHandle) {
+    handle.force_flush().await;
+    global::shutdown_tracer_provider();
+    if let Some(meter_provider) = METER_PROVIDER.get() {
+        let _ = meter_provider.shutdown();
+    }
+    #[cfg(feature = "logs")]
+    if let Some(logger_provider) = LOGGER_PROVIDER.get() {
+        let _ = logger_provider.shutdown();
+    }
+}